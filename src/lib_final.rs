@@ -1,4 +1,4 @@
-use image::{DynamicImage, ImageBuffer, ImageReader, Luma};
+use image::{DynamicImage, GenericImageView, ImageBuffer, ImageReader, Luma, LumaA};
 use natord::compare as natord_compare;
 use pyo3::prelude::*;
 use pyo3::types::PyModule;
@@ -6,9 +6,11 @@ use pyo3::{wrap_pyfunction, Bound};
 use rayon::prelude::*;
 use std::env;
 use std::cmp::min;
+use std::collections::HashMap;
 use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use thiserror::Error;
 use walkdir::WalkDir;
 
@@ -22,50 +24,102 @@ enum ThumbError {
     Empty,
     #[error("Dimension mismatch: expected {0}x{1}, got {2}x{3}")]
     Dim(usize, usize, usize, usize),
+    #[error("Invalid packed volume file: {0}")]
+    Volume(String),
 }
 
 fn to_pyerr(e: ThumbError) -> PyErr {
     pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
 }
 
-// Enum to handle different bit depths
+// Standard IEEE CRC32 (reflected, polynomial 0xEDB88320), table-driven.
+static CRC32_TABLE: OnceLock<[u32; 256]> = OnceLock::new();
+
+fn crc32_table() -> &'static [u32; 256] {
+    CRC32_TABLE.get_or_init(|| {
+        let mut table = [0u32; 256];
+        for (n, entry) in table.iter_mut().enumerate() {
+            let mut a = n as u32;
+            for _ in 0..8 {
+                a = if a & 1 == 1 { 0xEDB8_8320 ^ (a >> 1) } else { a >> 1 };
+            }
+            *entry = a;
+        }
+        table
+    })
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let table = crc32_table();
+    !bytes.iter().fold(0xFFFF_FFFFu32, |a, &b| (a >> 8) ^ table[((a ^ b as u32) & 0xFF) as usize])
+}
+
+// Enum to handle different bit depths. The LumaA variants keep an interleaved
+// (luma, alpha) pair per pixel so transparency (e.g. a segmentation mask) survives
+// the pyramid instead of being silently dropped.
 enum ImageDepth {
     Luma8(Vec<u8>),
     Luma16(Vec<u16>),
+    LumaA8(Vec<u8>),
+    LumaA16(Vec<u16>),
 }
 
 #[inline]
-fn to_luma_preserve_depth(img: DynamicImage) -> (ImageDepth, u32, u32) {
+fn to_luma_preserve_depth(img: DynamicImage, preserve_alpha: bool) -> (ImageDepth, u32, u32) {
     match img {
         // 8-bit images
         DynamicImage::ImageLuma8(gray) => {
             let (w, h) = gray.dimensions();
             (ImageDepth::Luma8(gray.into_raw()), w, h)
         },
+        DynamicImage::ImageLumaA8(gray_a) => {
+            let (w, h) = gray_a.dimensions();
+            if preserve_alpha {
+                (ImageDepth::LumaA8(gray_a.into_raw()), w, h)
+            } else {
+                let luma = gray_a.into_raw().chunks_exact(2).map(|p| p[0]).collect();
+                (ImageDepth::Luma8(luma), w, h)
+            }
+        },
         DynamicImage::ImageRgb8(_) => {
             let gray = img.to_luma8();
             let (w, h) = gray.dimensions();
             (ImageDepth::Luma8(gray.into_raw()), w, h)
         },
         DynamicImage::ImageRgba8(_) => {
-            let gray = img.to_luma8();
-            let (w, h) = gray.dimensions();
-            (ImageDepth::Luma8(gray.into_raw()), w, h)
+            let (w, h) = img.dimensions();
+            if preserve_alpha {
+                (ImageDepth::LumaA8(img.to_luma_alpha8().into_raw()), w, h)
+            } else {
+                (ImageDepth::Luma8(img.to_luma8().into_raw()), w, h)
+            }
         },
         // 16-bit images
         DynamicImage::ImageLuma16(gray) => {
             let (w, h) = gray.dimensions();
             (ImageDepth::Luma16(gray.into_raw()), w, h)
         },
+        DynamicImage::ImageLumaA16(gray_a) => {
+            let (w, h) = gray_a.dimensions();
+            if preserve_alpha {
+                (ImageDepth::LumaA16(gray_a.into_raw()), w, h)
+            } else {
+                let luma = gray_a.into_raw().chunks_exact(2).map(|p| p[0]).collect();
+                (ImageDepth::Luma16(luma), w, h)
+            }
+        },
         DynamicImage::ImageRgb16(_) => {
             let gray = img.to_luma16();
             let (w, h) = gray.dimensions();
             (ImageDepth::Luma16(gray.into_raw()), w, h)
         },
         DynamicImage::ImageRgba16(_) => {
-            let gray = img.to_luma16();
-            let (w, h) = gray.dimensions();
-            (ImageDepth::Luma16(gray.into_raw()), w, h)
+            let (w, h) = img.dimensions();
+            if preserve_alpha {
+                (ImageDepth::LumaA16(img.to_luma_alpha16().into_raw()), w, h)
+            } else {
+                (ImageDepth::Luma16(img.to_luma16().into_raw()), w, h)
+            }
         },
         // 32-bit float images - convert to 16-bit
         DynamicImage::ImageRgb32F(_) => {
@@ -74,9 +128,12 @@ fn to_luma_preserve_depth(img: DynamicImage) -> (ImageDepth, u32, u32) {
             (ImageDepth::Luma16(gray.into_raw()), w, h)
         },
         DynamicImage::ImageRgba32F(_) => {
-            let gray = img.to_luma16();
-            let (w, h) = gray.dimensions();
-            (ImageDepth::Luma16(gray.into_raw()), w, h)
+            let (w, h) = img.dimensions();
+            if preserve_alpha {
+                (ImageDepth::LumaA16(img.to_luma_alpha16().into_raw()), w, h)
+            } else {
+                (ImageDepth::Luma16(img.to_luma16().into_raw()), w, h)
+            }
         },
         _ => {
             // Default fallback to 8-bit
@@ -87,17 +144,41 @@ fn to_luma_preserve_depth(img: DynamicImage) -> (ImageDepth, u32, u32) {
     }
 }
 
+// How to handle an odd trailing row/column/slice. Drop matches the original
+// behavior (silently truncate); Clamp replicates the final source row/column so
+// the output dimension is `(s + 1) >> 1` instead of losing the boundary data.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EdgeMode {
+    Drop,
+    Clamp,
+}
+
+fn parse_edge_mode(s: Option<&str>) -> EdgeMode {
+    match s.map(|v| v.to_lowercase()) {
+        Some(ref m) if m == "clamp" => EdgeMode::Clamp,
+        _ => EdgeMode::Drop,
+    }
+}
+
+#[inline]
+fn half_dim(s: usize, mode: EdgeMode) -> usize {
+    match mode {
+        EdgeMode::Drop => s >> 1,
+        EdgeMode::Clamp => (s + 1) >> 1,
+    }
+}
+
 #[inline]
-fn downscale_half_u8(src: &[u8], sw: usize, _sh: usize, dst: &mut [u8]) {
-    let dw = sw >> 1;
+fn downscale_half_u8(src: &[u8], sw: usize, sh: usize, mode: EdgeMode, dst: &mut [u8]) {
+    let dw = half_dim(sw, mode);
     dst.par_chunks_mut(dw).enumerate().for_each(|(y, row)| {
         let sy0 = y << 1;
-        let sy1 = sy0 + 1;
+        let sy1 = if sy0 + 1 < sh { sy0 + 1 } else { sy0 };
         let base0 = sy0 * sw;
         let base1 = sy1 * sw;
         for x in 0..dw {
             let sx0 = x << 1;
-            let sx1 = sx0 + 1;
+            let sx1 = if sx0 + 1 < sw { sx0 + 1 } else { sx0 };
             let a = src[base0 + sx0] as u32;
             let b = src[base0 + sx1] as u32;
             let c = src[base1 + sx0] as u32;
@@ -108,16 +189,16 @@ fn downscale_half_u8(src: &[u8], sw: usize, _sh: usize, dst: &mut [u8]) {
 }
 
 #[inline]
-fn downscale_half_u16(src: &[u16], sw: usize, _sh: usize, dst: &mut [u16]) {
-    let dw = sw >> 1;
+fn downscale_half_u16(src: &[u16], sw: usize, sh: usize, mode: EdgeMode, dst: &mut [u16]) {
+    let dw = half_dim(sw, mode);
     dst.par_chunks_mut(dw).enumerate().for_each(|(y, row)| {
         let sy0 = y << 1;
-        let sy1 = sy0 + 1;
+        let sy1 = if sy0 + 1 < sh { sy0 + 1 } else { sy0 };
         let base0 = sy0 * sw;
         let base1 = sy1 * sw;
         for x in 0..dw {
             let sx0 = x << 1;
-            let sx1 = sx0 + 1;
+            let sx1 = if sx0 + 1 < sw { sx0 + 1 } else { sx0 };
             let a = src[base0 + sx0] as u32;
             let b = src[base0 + sx1] as u32;
             let c = src[base1 + sx0] as u32;
@@ -141,6 +222,97 @@ fn avg_two_u16_inplace(dst: &mut [u16], src: &[u16]) {
     });
 }
 
+// 2x2 spatial box-average for interleaved (luma, alpha) pairs. The luma channel is
+// weighted by alpha so fully-transparent source pixels don't pollute the thumbnail;
+// the alpha channel is box-averaged independently. Falls back to a plain average
+// when all four source pixels are fully transparent.
+#[inline]
+fn downscale_half_lumaa8(src: &[u8], sw: usize, sh: usize, mode: EdgeMode, dst: &mut [u8]) {
+    let dw = half_dim(sw, mode);
+    dst.par_chunks_mut(dw * 2).enumerate().for_each(|(y, row)| {
+        let sy0 = y << 1;
+        let sy1 = if sy0 + 1 < sh { sy0 + 1 } else { sy0 };
+        let base0 = sy0 * sw * 2;
+        let base1 = sy1 * sw * 2;
+        for x in 0..dw {
+            let sx0 = x << 1;
+            let sx1 = if sx0 + 1 < sw { sx0 + 1 } else { sx0 };
+            let (l0, a0) = (src[base0 + sx0 * 2] as u32, src[base0 + sx0 * 2 + 1] as u32);
+            let (l1, a1) = (src[base0 + sx1 * 2] as u32, src[base0 + sx1 * 2 + 1] as u32);
+            let (l2, a2) = (src[base1 + sx0 * 2] as u32, src[base1 + sx0 * 2 + 1] as u32);
+            let (l3, a3) = (src[base1 + sx1 * 2] as u32, src[base1 + sx1 * 2 + 1] as u32);
+            let alpha_sum = a0 + a1 + a2 + a3;
+            let luma = if alpha_sum > 0 {
+                (l0 * a0 + l1 * a1 + l2 * a2 + l3 * a3 + alpha_sum / 2) / alpha_sum
+            } else {
+                (l0 + l1 + l2 + l3 + 2) >> 2
+            };
+            row[x * 2] = luma as u8;
+            row[x * 2 + 1] = ((alpha_sum + 2) >> 2) as u8;
+        }
+    });
+}
+
+#[inline]
+fn downscale_half_lumaa16(src: &[u16], sw: usize, sh: usize, mode: EdgeMode, dst: &mut [u16]) {
+    let dw = half_dim(sw, mode);
+    dst.par_chunks_mut(dw * 2).enumerate().for_each(|(y, row)| {
+        let sy0 = y << 1;
+        let sy1 = if sy0 + 1 < sh { sy0 + 1 } else { sy0 };
+        let base0 = sy0 * sw * 2;
+        let base1 = sy1 * sw * 2;
+        for x in 0..dw {
+            let sx0 = x << 1;
+            let sx1 = if sx0 + 1 < sw { sx0 + 1 } else { sx0 };
+            let (l0, a0) = (src[base0 + sx0 * 2] as u64, src[base0 + sx0 * 2 + 1] as u64);
+            let (l1, a1) = (src[base0 + sx1 * 2] as u64, src[base0 + sx1 * 2 + 1] as u64);
+            let (l2, a2) = (src[base1 + sx0 * 2] as u64, src[base1 + sx0 * 2 + 1] as u64);
+            let (l3, a3) = (src[base1 + sx1 * 2] as u64, src[base1 + sx1 * 2 + 1] as u64);
+            let alpha_sum = a0 + a1 + a2 + a3;
+            let luma = if alpha_sum > 0 {
+                (l0 * a0 + l1 * a1 + l2 * a2 + l3 * a3 + alpha_sum / 2) / alpha_sum
+            } else {
+                (l0 + l1 + l2 + l3 + 2) >> 2
+            };
+            row[x * 2] = luma as u16;
+            row[x * 2 + 1] = ((alpha_sum + 2) >> 2) as u16;
+        }
+    });
+}
+
+// Pairwise z-average for interleaved (luma, alpha) pairs, same alpha-weighting as above
+#[inline]
+fn avg_two_lumaa8_inplace(dst: &mut [u8], src: &[u8]) {
+    dst.par_chunks_mut(2).zip(src.par_chunks(2)).for_each(|(d, s)| {
+        let (l0, a0) = (d[0] as u32, d[1] as u32);
+        let (l1, a1) = (s[0] as u32, s[1] as u32);
+        let alpha_sum = a0 + a1;
+        let luma = if alpha_sum > 0 {
+            (l0 * a0 + l1 * a1 + alpha_sum / 2) / alpha_sum
+        } else {
+            (l0 + l1 + 1) >> 1
+        };
+        d[0] = luma as u8;
+        d[1] = ((alpha_sum + 1) >> 1) as u8;
+    });
+}
+
+#[inline]
+fn avg_two_lumaa16_inplace(dst: &mut [u16], src: &[u16]) {
+    dst.par_chunks_mut(2).zip(src.par_chunks(2)).for_each(|(d, s)| {
+        let (l0, a0) = (d[0] as u64, d[1] as u64);
+        let (l1, a1) = (s[0] as u64, s[1] as u64);
+        let alpha_sum = a0 + a1;
+        let luma = if alpha_sum > 0 {
+            (l0 * a0 + l1 * a1 + alpha_sum / 2) / alpha_sum
+        } else {
+            (l0 + l1 + 1) >> 1
+        };
+        d[0] = luma as u16;
+        d[1] = ((alpha_sum + 1) >> 1) as u16;
+    });
+}
+
 fn list_slices_sorted(input_dir: &Path) -> Result<Vec<PathBuf>, ThumbError> {
     let mut files: Vec<_> = WalkDir::new(input_dir)
         .min_depth(1)
@@ -167,9 +339,9 @@ fn list_slices_sorted(input_dir: &Path) -> Result<Vec<PathBuf>, ThumbError> {
     Ok(files)
 }
 
-fn read_luma_preserve_depth(path: &Path) -> Result<(usize, usize, ImageDepth), ThumbError> {
+fn read_luma_preserve_depth(path: &Path, preserve_alpha: bool) -> Result<(usize, usize, ImageDepth), ThumbError> {
     let img = ImageReader::open(path)?.with_guessed_format()?.decode()?;
-    let (depth, w, h) = to_luma_preserve_depth(img);
+    let (depth, w, h) = to_luma_preserve_depth(img, preserve_alpha);
     Ok((w as usize, h as usize, depth))
 }
 
@@ -184,11 +356,93 @@ fn write_tiff_preserve_depth(path: &Path, w: u32, h: u32, depth: &ImageDepth) ->
             let img = ImageBuffer::<Luma<u16>, _>::from_raw(w, h, buf.to_vec())
                 .ok_or_else(|| ThumbError::Dim(w as usize, h as usize, 0, 0))?;
             img.save(path)?;
+        },
+        ImageDepth::LumaA8(buf) => {
+            let img = ImageBuffer::<LumaA<u8>, _>::from_raw(w, h, buf.to_vec())
+                .ok_or_else(|| ThumbError::Dim(w as usize, h as usize, 0, 0))?;
+            img.save(path)?;
+        },
+        ImageDepth::LumaA16(buf) => {
+            let img = ImageBuffer::<LumaA<u16>, _>::from_raw(w, h, buf.to_vec())
+                .ok_or_else(|| ThumbError::Dim(w as usize, h as usize, 0, 0))?;
+            img.save(path)?;
+        }
+    }
+    Ok(())
+}
+
+fn crc32_of_depth(depth: &ImageDepth) -> u32 {
+    match depth {
+        ImageDepth::Luma8(buf) => crc32(buf),
+        ImageDepth::Luma16(buf) => {
+            let bytes: Vec<u8> = buf.iter().flat_map(|v| v.to_be_bytes()).collect();
+            crc32(&bytes)
+        },
+        ImageDepth::LumaA8(buf) => crc32(buf),
+        ImageDepth::LumaA16(buf) => {
+            let bytes: Vec<u8> = buf.iter().flat_map(|v| v.to_be_bytes()).collect();
+            crc32(&bytes)
+        },
+    }
+}
+
+fn manifest_path(level_dir: &Path) -> PathBuf {
+    level_dir.join("manifest.txt")
+}
+
+// Sidecar mapping output filename -> CRC32 (hex) of its pixel buffer, one "name crc" per line
+fn read_manifest(level_dir: &Path) -> HashMap<String, u32> {
+    let mut map = HashMap::new();
+    if let Ok(contents) = fs::read_to_string(manifest_path(level_dir)) {
+        for line in contents.lines() {
+            if let Some((name, crc_hex)) = line.split_once(' ') {
+                if let Ok(crc) = u32::from_str_radix(crc_hex.trim(), 16) {
+                    map.insert(name.to_string(), crc);
+                }
+            }
+        }
+    }
+    map
+}
+
+// Write a full level manifest (name -> CRC32) in one pass, atomically via rename
+fn write_manifest(level_dir: &Path, map: &HashMap<String, u32>) -> Result<(), ThumbError> {
+    let path = manifest_path(level_dir);
+    let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        for (entry_name, entry_crc) in map {
+            writeln!(file, "{} {:08x}", entry_name, entry_crc)?;
         }
     }
+    fs::rename(&tmp_path, &path)?;
     Ok(())
 }
 
+// Re-decode an existing output and compare its CRC against the manifest's recorded value
+fn verify_tile_crc(path: &Path, expected: u32, preserve_alpha: bool) -> bool {
+    match read_luma_preserve_depth(path, preserve_alpha) {
+        Ok((_, _, depth)) => crc32_of_depth(&depth) == expected,
+        Err(_) => false,
+    }
+}
+
+// Count leading tiles (0..pairs_expected) that exist on disk and verify against the manifest,
+// stopping at the first missing/corrupt entry so the resume index stays contiguous.
+fn count_verified_completed(out_dir: &Path, pairs_expected: usize, preserve_alpha: bool) -> usize {
+    let manifest = read_manifest(out_dir);
+    let mut count = 0;
+    for pair_idx in 0..pairs_expected {
+        let name = format!("{:06}.tif", pair_idx);
+        let path = out_dir.join(&name);
+        match manifest.get(&name) {
+            Some(&crc) if path.exists() && verify_tile_crc(&path, crc, preserve_alpha) => count += 1,
+            _ => break,
+        }
+    }
+    count
+}
+
 fn ensure_dir(p: &Path) -> Result<(), ThumbError> {
     fs::create_dir_all(p)?;
     Ok(())
@@ -200,21 +454,24 @@ fn level_dir(base: &Path, level: usize) -> PathBuf {
 
 /// Plan levels for thumbnail generation
 /// Returns (max_level, [(pairs_count, weight)...])
-fn plan_levels(n0: usize, mut w: usize, mut h: usize) -> (usize, Vec<(usize, f64)>) {
+fn plan_levels(n0: usize, mut w: usize, mut h: usize, edge_mode: EdgeMode) -> (usize, Vec<(usize, f64)>) {
     let mut level = 1usize;
     let mut units: Vec<(usize, f64)> = Vec::new();
     let mut remaining_n = n0;
     let mut weight = 1.0f64;
 
     loop {
-        let pairs = remaining_n / 2;
+        let pairs = match edge_mode {
+            EdgeMode::Drop => remaining_n / 2,
+            EdgeMode::Clamp => (remaining_n + 1) / 2,
+        };
         if pairs == 0 {
             break;
         }
         units.push((pairs, weight));
 
-        w >>= 1;
-        h >>= 1;
+        w = half_dim(w, edge_mode);
+        h = half_dim(h, edge_mode);
         remaining_n = pairs;
 
         if w <= 500 && h <= 500 {
@@ -226,6 +483,10 @@ fn plan_levels(n0: usize, mut w: usize, mut h: usize) -> (usize, Vec<(usize, f64
     (level, units)
 }
 
+// How many tiles to process between manifest flushes within a level; bounds how much
+// resume work a crash mid-level can cost without paying the per-tile rewrite cost.
+const MANIFEST_FLUSH_INTERVAL: usize = 256;
+
 fn percent(done_units: f64, total_units: f64) -> f64 {
     if total_units <= 0.0 {
         100.0
@@ -234,19 +495,6 @@ fn percent(done_units: f64, total_units: f64) -> f64 {
     }
 }
 
-fn completed_outputs_in_level(dir: &Path) -> usize {
-    if !dir.exists() {
-        return 0;
-    }
-    WalkDir::new(dir)
-        .min_depth(1)
-        .max_depth(1)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .count()
-}
-
 /// Process one level with progress callback
 fn process_level_with_callback(
     input_files: &[PathBuf],
@@ -258,20 +506,32 @@ fn process_level_with_callback(
     total_units: f64,
     done_units: Arc<Mutex<f64>>,
     py_callback: Option<&PyObject>,
+    preserve_alpha: bool,
+    edge_mode: EdgeMode,
 ) -> Result<usize, ThumbError> {
     ensure_dir(out_dir)?;
-    let total_pairs = input_files.len() / 2;
+    let n = input_files.len();
+    let total_pairs = match edge_mode {
+        EdgeMode::Drop => n / 2,
+        // An odd trailing slice still becomes its own output, carried forward unpaired.
+        EdgeMode::Clamp => (n + 1) / 2,
+    };
     if total_pairs == 0 {
         return Ok(0);
     }
 
-    let dw = input_w >> 1;
-    let dh = input_h >> 1;
+    let dw = half_dim(input_w, edge_mode);
+    let dh = half_dim(input_h, edge_mode);
     let unit_per_pair = weight;
 
     let from = min(start_pair_idx, total_pairs);
     let to = total_pairs;
 
+    // Accumulate manifest entries in memory and flush every MANIFEST_FLUSH_INTERVAL tiles
+    // (plus once more at the end), rather than re-parsing and rewriting the whole manifest
+    // file after every tile or deferring it all the way to end-of-level.
+    let mut manifest = read_manifest(out_dir);
+
     let mut last_reported_pct = -1.0;
 
     // Process pairs sequentially for real-time progress updates
@@ -280,55 +540,127 @@ fn process_level_with_callback(
         let i0 = pair_i * 2;
         let i1 = i0 + 1;
 
-        let (w0, h0, depth0) = read_luma_preserve_depth(&input_files[i0])?;
-        let (w1, h1, depth1) = read_luma_preserve_depth(&input_files[i1])?;
-
-        if w0 != input_w || h0 != input_h || w1 != input_w || h1 != input_h {
+        let (w0, h0, depth0) = read_luma_preserve_depth(&input_files[i0], preserve_alpha)?;
+        if w0 != input_w || h0 != input_h {
             return Err(ThumbError::Dim(input_w, input_h, w0, h0));
         }
 
-        // Process based on bit depth
-        let result_depth = match (depth0, depth1) {
-            (ImageDepth::Luma8(buf0), ImageDepth::Luma8(buf1)) => {
-                let mut d0 = vec![0u8; dw * dh];
-                let mut d1 = vec![0u8; dw * dh];
-                downscale_half_u8(&buf0, input_w, input_h, &mut d0);
-                downscale_half_u8(&buf1, input_w, input_h, &mut d1);
-                avg_two_u8_inplace(&mut d0, &d1);
-                ImageDepth::Luma8(d0)
-            },
-            (ImageDepth::Luma16(buf0), ImageDepth::Luma16(buf1)) => {
-                let mut d0 = vec![0u16; dw * dh];
-                let mut d1 = vec![0u16; dw * dh];
-                downscale_half_u16(&buf0, input_w, input_h, &mut d0);
-                downscale_half_u16(&buf1, input_w, input_h, &mut d1);
-                avg_two_u16_inplace(&mut d0, &d1);
-                ImageDepth::Luma16(d0)
-            },
-            // Mixed depth - convert to 16-bit
-            (ImageDepth::Luma8(buf0), ImageDepth::Luma16(buf1)) => {
-                let buf0_16: Vec<u16> = buf0.iter().map(|&x| (x as u16) << 8).collect();
-                let mut d0 = vec![0u16; dw * dh];
-                let mut d1 = vec![0u16; dw * dh];
-                downscale_half_u16(&buf0_16, input_w, input_h, &mut d0);
-                downscale_half_u16(&buf1, input_w, input_h, &mut d1);
-                avg_two_u16_inplace(&mut d0, &d1);
-                ImageDepth::Luma16(d0)
-            },
-            (ImageDepth::Luma16(buf0), ImageDepth::Luma8(buf1)) => {
-                let buf1_16: Vec<u16> = buf1.iter().map(|&x| (x as u16) << 8).collect();
-                let mut d0 = vec![0u16; dw * dh];
-                let mut d1 = vec![0u16; dw * dh];
-                downscale_half_u16(&buf0, input_w, input_h, &mut d0);
-                downscale_half_u16(&buf1_16, input_w, input_h, &mut d1);
-                avg_two_u16_inplace(&mut d0, &d1);
-                ImageDepth::Luma16(d0)
+        let result_depth = if i1 < n {
+            let (w1, h1, depth1) = read_luma_preserve_depth(&input_files[i1], preserve_alpha)?;
+            if w1 != input_w || h1 != input_h {
+                return Err(ThumbError::Dim(input_w, input_h, w1, h1));
+            }
+
+            // Process based on bit depth
+            match (depth0, depth1) {
+                (ImageDepth::Luma8(buf0), ImageDepth::Luma8(buf1)) => {
+                    let mut d0 = vec![0u8; dw * dh];
+                    let mut d1 = vec![0u8; dw * dh];
+                    downscale_half_u8(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    downscale_half_u8(&buf1, input_w, input_h, edge_mode, &mut d1);
+                    avg_two_u8_inplace(&mut d0, &d1);
+                    ImageDepth::Luma8(d0)
+                },
+                (ImageDepth::Luma16(buf0), ImageDepth::Luma16(buf1)) => {
+                    let mut d0 = vec![0u16; dw * dh];
+                    let mut d1 = vec![0u16; dw * dh];
+                    downscale_half_u16(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    downscale_half_u16(&buf1, input_w, input_h, edge_mode, &mut d1);
+                    avg_two_u16_inplace(&mut d0, &d1);
+                    ImageDepth::Luma16(d0)
+                },
+                // Mixed depth - convert to 16-bit
+                (ImageDepth::Luma8(buf0), ImageDepth::Luma16(buf1)) => {
+                    let buf0_16: Vec<u16> = buf0.iter().map(|&x| (x as u16) << 8).collect();
+                    let mut d0 = vec![0u16; dw * dh];
+                    let mut d1 = vec![0u16; dw * dh];
+                    downscale_half_u16(&buf0_16, input_w, input_h, edge_mode, &mut d0);
+                    downscale_half_u16(&buf1, input_w, input_h, edge_mode, &mut d1);
+                    avg_two_u16_inplace(&mut d0, &d1);
+                    ImageDepth::Luma16(d0)
+                },
+                (ImageDepth::Luma16(buf0), ImageDepth::Luma8(buf1)) => {
+                    let buf1_16: Vec<u16> = buf1.iter().map(|&x| (x as u16) << 8).collect();
+                    let mut d0 = vec![0u16; dw * dh];
+                    let mut d1 = vec![0u16; dw * dh];
+                    downscale_half_u16(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    downscale_half_u16(&buf1_16, input_w, input_h, edge_mode, &mut d1);
+                    avg_two_u16_inplace(&mut d0, &d1);
+                    ImageDepth::Luma16(d0)
+                },
+                (ImageDepth::LumaA8(buf0), ImageDepth::LumaA8(buf1)) => {
+                    let mut d0 = vec![0u8; dw * dh * 2];
+                    let mut d1 = vec![0u8; dw * dh * 2];
+                    downscale_half_lumaa8(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    downscale_half_lumaa8(&buf1, input_w, input_h, edge_mode, &mut d1);
+                    avg_two_lumaa8_inplace(&mut d0, &d1);
+                    ImageDepth::LumaA8(d0)
+                },
+                (ImageDepth::LumaA16(buf0), ImageDepth::LumaA16(buf1)) => {
+                    let mut d0 = vec![0u16; dw * dh * 2];
+                    let mut d1 = vec![0u16; dw * dh * 2];
+                    downscale_half_lumaa16(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    downscale_half_lumaa16(&buf1, input_w, input_h, edge_mode, &mut d1);
+                    avg_two_lumaa16_inplace(&mut d0, &d1);
+                    ImageDepth::LumaA16(d0)
+                },
+                // Mixed LumaA depth - convert to 16-bit
+                (ImageDepth::LumaA8(buf0), ImageDepth::LumaA16(buf1)) => {
+                    let buf0_16: Vec<u16> = buf0.iter().map(|&x| (x as u16) << 8).collect();
+                    let mut d0 = vec![0u16; dw * dh * 2];
+                    let mut d1 = vec![0u16; dw * dh * 2];
+                    downscale_half_lumaa16(&buf0_16, input_w, input_h, edge_mode, &mut d0);
+                    downscale_half_lumaa16(&buf1, input_w, input_h, edge_mode, &mut d1);
+                    avg_two_lumaa16_inplace(&mut d0, &d1);
+                    ImageDepth::LumaA16(d0)
+                },
+                (ImageDepth::LumaA16(buf0), ImageDepth::LumaA8(buf1)) => {
+                    let buf1_16: Vec<u16> = buf1.iter().map(|&x| (x as u16) << 8).collect();
+                    let mut d0 = vec![0u16; dw * dh * 2];
+                    let mut d1 = vec![0u16; dw * dh * 2];
+                    downscale_half_lumaa16(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    downscale_half_lumaa16(&buf1_16, input_w, input_h, edge_mode, &mut d1);
+                    avg_two_lumaa16_inplace(&mut d0, &d1);
+                    ImageDepth::LumaA16(d0)
+                },
+                // preserve_alpha is constant for a run, so luma/luma-alpha can't mix in practice
+                _ => return Err(ThumbError::Dim(input_w, input_h, 0, 0)),
+            }
+        } else {
+            // Odd trailing slice under EdgeMode::Clamp: carry it forward downscaled but
+            // not z-averaged, since it has no partner to average against.
+            match depth0 {
+                ImageDepth::Luma8(buf0) => {
+                    let mut d0 = vec![0u8; dw * dh];
+                    downscale_half_u8(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    ImageDepth::Luma8(d0)
+                },
+                ImageDepth::Luma16(buf0) => {
+                    let mut d0 = vec![0u16; dw * dh];
+                    downscale_half_u16(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    ImageDepth::Luma16(d0)
+                },
+                ImageDepth::LumaA8(buf0) => {
+                    let mut d0 = vec![0u8; dw * dh * 2];
+                    downscale_half_lumaa8(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    ImageDepth::LumaA8(d0)
+                },
+                ImageDepth::LumaA16(buf0) => {
+                    let mut d0 = vec![0u16; dw * dh * 2];
+                    downscale_half_lumaa16(&buf0, input_w, input_h, edge_mode, &mut d0);
+                    ImageDepth::LumaA16(d0)
+                },
             }
         };
 
         let out_name = format!("{:06}.tif", pair_i);
-        let out_path = out_dir.join(out_name);
+        let out_path = out_dir.join(&out_name);
         write_tiff_preserve_depth(&out_path, dw as u32, dh as u32, &result_depth)?;
+        manifest.insert(out_name, crc32_of_depth(&result_depth));
+
+        if (pair_idx - from + 1) % MANIFEST_FLUSH_INTERVAL == 0 {
+            write_manifest(out_dir, &manifest)?;
+        }
 
         // Update progress after each pair
         let new_done = {
@@ -352,6 +684,8 @@ fn process_level_with_callback(
         }
     }
 
+    write_manifest(out_dir, &manifest)?;
+
     Ok(to - from)
 }
 
@@ -365,8 +699,13 @@ fn process_level_with_callback(
 /// * `seq_begin` - Optional starting sequence number
 /// * `seq_end` - Optional ending sequence number
 /// * `index_length` - Optional number of digits in sequence
+/// * `preserve_alpha` - Keep the alpha channel of RGBA/LumaA inputs instead of
+///   collapsing to plain luma (default: false, matching prior behavior)
+/// * `edge_mode` - "drop" (default, matching prior behavior) truncates a trailing odd
+///   row/column/slice; "clamp" replicates the final source row/column and carries the
+///   final unpaired slice forward instead of discarding it
 #[pyfunction]
-#[pyo3(signature = (input_dir, py_progress_cb=None, prefix=None, file_type=None, seq_begin=None, seq_end=None, index_length=None))]
+#[pyo3(signature = (input_dir, py_progress_cb=None, prefix=None, file_type=None, seq_begin=None, seq_end=None, index_length=None, preserve_alpha=false, edge_mode=None))]
 fn build_thumbnails(
     input_dir: String,
     py_progress_cb: Option<PyObject>,
@@ -375,7 +714,10 @@ fn build_thumbnails(
     seq_begin: Option<usize>,
     seq_end: Option<usize>,
     index_length: Option<usize>,
+    preserve_alpha: bool,
+    edge_mode: Option<String>,
 ) -> PyResult<()> {
+    let edge_mode = parse_edge_mode(edge_mode.as_deref());
     let input_dir = PathBuf::from(&input_dir);
 
     // Get all image files and filter by pattern if provided
@@ -403,13 +745,13 @@ fn build_thumbnails(
         return Ok(());
     }
 
-    let (w0, h0, _) = read_luma_preserve_depth(&files[0]).map_err(to_pyerr)?;
+    let (w0, h0, _) = read_luma_preserve_depth(&files[0], preserve_alpha).map_err(to_pyerr)?;
     let n0 = files.len();
 
     let base_out = input_dir.join(".thumbnail");
     ensure_dir(&base_out).map_err(to_pyerr)?;
 
-    let (_max_level, units) = plan_levels(n0, w0, h0);
+    let (_max_level, units) = plan_levels(n0, w0, h0, edge_mode);
     let total_units: f64 = units.iter().map(|(pairs, w)| *pairs as f64 * *w).sum();
 
     if total_units == 0.0 {
@@ -435,8 +777,9 @@ fn build_thumbnails(
         let out_dir = level_dir(&base_out, level_no);
         ensure_dir(&out_dir).map_err(to_pyerr)?;
 
-        // Check for already completed work (resume support)
-        let already = completed_outputs_in_level(&out_dir);
+        // Check for already completed work (resume support), verified against the CRC manifest
+        // so a tile left half-written by a killed process is reprocessed rather than trusted.
+        let already = count_verified_completed(&out_dir, *pairs_expected, preserve_alpha);
         let already_clamped = min(already, *pairs_expected);
 
         if already_clamped > 0 {
@@ -449,8 +792,8 @@ fn build_thumbnails(
             // Level already complete, move to next
             let next_files = list_slices_sorted(&out_dir).map_err(to_pyerr)?;
             cur_files = next_files;
-            cur_w >>= 1;
-            cur_h >>= 1;
+            cur_w = half_dim(cur_w, edge_mode);
+            cur_h = half_dim(cur_h, edge_mode);
 
             if cur_w <= 500 && cur_h <= 500 {
                 break;
@@ -469,14 +812,16 @@ fn build_thumbnails(
             total_units,
             done_units.clone(),
             py_progress_cb.as_ref(),
+            preserve_alpha,
+            edge_mode,
         )
         .map_err(to_pyerr)?;
 
         // Prepare for next level
         let next_files = list_slices_sorted(&out_dir).map_err(to_pyerr)?;
         cur_files = next_files;
-        cur_w >>= 1;
-        cur_h >>= 1;
+        cur_w = half_dim(cur_w, edge_mode);
+        cur_h = half_dim(cur_h, edge_mode);
 
         if cur_w <= 500 && cur_h <= 500 {
             break;
@@ -493,8 +838,324 @@ fn build_thumbnails(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Packed single-file-per-level volume container, as an alternative to writing
+// one {:06}.tif per pair. Header layout (all fields big-endian):
+//   magic: [u8; 4] = b"CTVL"
+//   version: u16
+//   bit_depth: u16   (8 or 16)
+//   width: u32
+//   height: u32
+//   slice_count: u32
+// followed by `slice_count` u64 byte-offsets (one per slice), then the raw
+// slice payloads back-to-back. Fixed-size header + offset table gives O(1)
+// access to any slice without decoding the rest of the file.
+// ---------------------------------------------------------------------------
+
+const VOLUME_MAGIC: &[u8; 4] = b"CTVL";
+const VOLUME_VERSION: u16 = 1;
+const VOLUME_HEADER_LEN: u64 = 4 + 2 + 2 + 4 + 4 + 4;
+
+fn depth_to_bytes(depth: ImageDepth) -> Result<(Vec<u8>, u16), ThumbError> {
+    match depth {
+        ImageDepth::Luma8(buf) => Ok((buf, 8)),
+        ImageDepth::Luma16(buf) => {
+            let bytes: Vec<u8> = buf.iter().flat_map(|v| v.to_be_bytes()).collect();
+            Ok((bytes, 16))
+        },
+        ImageDepth::LumaA8(_) | ImageDepth::LumaA16(_) => Err(ThumbError::Volume(
+            "packed volume output does not support alpha-preserving input".to_string(),
+        )),
+    }
+}
+
+fn widen_to_u16(buf: &[u8], bit_depth: u16) -> Vec<u16> {
+    if bit_depth == 8 {
+        buf.iter().map(|&x| (x as u16) << 8).collect()
+    } else {
+        buf.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect()
+    }
+}
+
+// Box-average+z-average a pair of same-sized slices, promoting to 16-bit if either is
+fn downsample_pair(buf0: &[u8], bd0: u16, buf1: &[u8], bd1: u16, w: usize, h: usize) -> (Vec<u8>, u16) {
+    let dw = w >> 1;
+    let dh = h >> 1;
+    if bd0 == 8 && bd1 == 8 {
+        let mut d0 = vec![0u8; dw * dh];
+        let mut d1 = vec![0u8; dw * dh];
+        downscale_half_u8(buf0, w, h, EdgeMode::Drop, &mut d0);
+        downscale_half_u8(buf1, w, h, EdgeMode::Drop, &mut d1);
+        avg_two_u8_inplace(&mut d0, &d1);
+        (d0, 8)
+    } else {
+        let v0 = widen_to_u16(buf0, bd0);
+        let v1 = widen_to_u16(buf1, bd1);
+        let mut d0 = vec![0u16; dw * dh];
+        let mut d1 = vec![0u16; dw * dh];
+        downscale_half_u16(&v0, w, h, EdgeMode::Drop, &mut d0);
+        downscale_half_u16(&v1, w, h, EdgeMode::Drop, &mut d1);
+        avg_two_u16_inplace(&mut d0, &d1);
+        let bytes: Vec<u8> = d0.iter().flat_map(|v| v.to_be_bytes()).collect();
+        (bytes, 16)
+    }
+}
+
+fn decode_pair_from_files(path0: &Path, path1: &Path, input_w: usize, input_h: usize) -> Result<(Vec<u8>, u16), ThumbError> {
+    let (w0, h0, depth0) = read_luma_preserve_depth(path0, false)?;
+    let (w1, h1, depth1) = read_luma_preserve_depth(path1, false)?;
+    if w0 != input_w || h0 != input_h || w1 != input_w || h1 != input_h {
+        return Err(ThumbError::Dim(input_w, input_h, w0, h0));
+    }
+    let (b0, bd0) = depth_to_bytes(depth0)?;
+    let (b1, bd1) = depth_to_bytes(depth1)?;
+    Ok(downsample_pair(&b0, bd0, &b1, bd1, input_w, input_h))
+}
+
+// Write a level's slices as one packed .vol file, atomically via rename
+fn write_volume_file(path: &Path, width: u32, height: u32, bit_depth: u16, slices: &[Vec<u8>]) -> Result<(), ThumbError> {
+    let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(VOLUME_MAGIC)?;
+        file.write_all(&VOLUME_VERSION.to_be_bytes())?;
+        file.write_all(&bit_depth.to_be_bytes())?;
+        file.write_all(&width.to_be_bytes())?;
+        file.write_all(&height.to_be_bytes())?;
+        file.write_all(&(slices.len() as u32).to_be_bytes())?;
+
+        let mut offset = VOLUME_HEADER_LEN + (slices.len() as u64) * 8;
+        let mut offsets = Vec::with_capacity(slices.len());
+        for s in slices {
+            offsets.push(offset);
+            offset += s.len() as u64;
+        }
+        for off in &offsets {
+            file.write_all(&off.to_be_bytes())?;
+        }
+        for s in slices {
+            file.write_all(s)?;
+        }
+    }
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+// Read a .vol file's header: (bit_depth, width, height, slice_count)
+fn read_volume_header(path: &Path) -> Result<(u16, u32, u32, u32), ThumbError> {
+    let mut file = fs::File::open(path)?;
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic)?;
+    if &magic != VOLUME_MAGIC {
+        return Err(ThumbError::Volume("bad magic".to_string()));
+    }
+    let mut b2 = [0u8; 2];
+    file.read_exact(&mut b2)?; // version, unused for now
+    file.read_exact(&mut b2)?;
+    let bit_depth = u16::from_be_bytes(b2);
+    let mut b4 = [0u8; 4];
+    file.read_exact(&mut b4)?;
+    let width = u32::from_be_bytes(b4);
+    file.read_exact(&mut b4)?;
+    let height = u32::from_be_bytes(b4);
+    file.read_exact(&mut b4)?;
+    let slice_count = u32::from_be_bytes(b4);
+    Ok((bit_depth, width, height, slice_count))
+}
+
+// Decode one slice out of a .vol file via its offset table, without touching the rest of the file
+fn read_volume_slice_bytes(path: &Path, index: usize) -> Result<(Vec<u8>, u32, u32, u16), ThumbError> {
+    let mut file = fs::File::open(path)?;
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic)?;
+    if &magic != VOLUME_MAGIC {
+        return Err(ThumbError::Volume("bad magic".to_string()));
+    }
+    let mut b2 = [0u8; 2];
+    file.read_exact(&mut b2)?; // version, unused for now
+    file.read_exact(&mut b2)?;
+    let bit_depth = u16::from_be_bytes(b2);
+    let mut b4 = [0u8; 4];
+    file.read_exact(&mut b4)?;
+    let width = u32::from_be_bytes(b4);
+    file.read_exact(&mut b4)?;
+    let height = u32::from_be_bytes(b4);
+    file.read_exact(&mut b4)?;
+    let slice_count = u32::from_be_bytes(b4);
+
+    if index >= slice_count as usize {
+        return Err(ThumbError::Volume(format!(
+            "slice index {} out of range (slice_count {})",
+            index, slice_count
+        )));
+    }
+
+    file.seek(SeekFrom::Start(VOLUME_HEADER_LEN + (index as u64) * 8))?;
+    let mut off_buf = [0u8; 8];
+    file.read_exact(&mut off_buf)?;
+    let slice_offset = u64::from_be_bytes(off_buf);
+
+    let slice_len = (width as u64) * (height as u64) * ((bit_depth / 8) as u64);
+    file.seek(SeekFrom::Start(slice_offset))?;
+    let mut data = vec![0u8; slice_len as usize];
+    file.read_exact(&mut data)?;
+    Ok((data, width, height, bit_depth))
+}
+
+/// Build thumbnails as one packed `.vol` file per level instead of one TIFF per tile.
+/// Much faster to enumerate/load (no per-tile filesystem walk) at the cost of not
+/// being individually viewable as images; see `read_volume_slice` for random access.
+#[pyfunction]
+#[pyo3(signature = (input_dir, py_progress_cb=None, prefix=None, file_type=None, seq_begin=None, seq_end=None, index_length=None))]
+fn build_thumbnails_volume(
+    input_dir: String,
+    py_progress_cb: Option<PyObject>,
+    prefix: Option<String>,
+    file_type: Option<String>,
+    seq_begin: Option<usize>,
+    seq_end: Option<usize>,
+    index_length: Option<usize>,
+) -> PyResult<()> {
+    let input_dir = PathBuf::from(&input_dir);
+
+    let files = if let (Some(prefix), Some(file_type), Some(seq_begin), Some(seq_end), Some(index_length)) =
+        (prefix.as_ref(), file_type.as_ref(), seq_begin, seq_end, index_length) {
+        let mut file_list = Vec::new();
+        for seq in seq_begin..=seq_end {
+            let filename = format!("{}{:0width$}.{}", prefix, seq, file_type, width = index_length);
+            let filepath = input_dir.join(&filename);
+            if filepath.exists() {
+                file_list.push(filepath);
+            }
+        }
+        file_list
+    } else {
+        list_slices_sorted(&input_dir).map_err(to_pyerr)?
+    };
+
+    if files.is_empty() {
+        if let Some(cb) = &py_progress_cb {
+            Python::with_gil(|py| { let _ = cb.call1(py, (100.0_f64,)); });
+        }
+        return Ok(());
+    }
+
+    let (w0, h0, _) = read_luma_preserve_depth(&files[0], false).map_err(to_pyerr)?;
+    let n0 = files.len();
+
+    let base_out = input_dir.join(".thumbnail");
+    ensure_dir(&base_out).map_err(to_pyerr)?;
+
+    let (_max_level, units) = plan_levels(n0, w0, h0, EdgeMode::Drop);
+    let total_units: f64 = units.iter().map(|(pairs, w)| *pairs as f64 * *w).sum();
+
+    if total_units == 0.0 {
+        if let Some(cb) = &py_progress_cb {
+            Python::with_gil(|py| { let _ = cb.call1(py, (100.0_f64,)); });
+        }
+        return Ok(());
+    }
+
+    let done_units = Arc::new(Mutex::new(0.0_f64));
+
+    if let Some(cb) = &py_progress_cb {
+        Python::with_gil(|py| { let _ = cb.call1(py, (0.0_f64,)); });
+    }
+
+    let mut cur_w = w0;
+    let mut cur_h = h0;
+
+    for (level_idx, (pairs_expected, weight)) in units.iter().enumerate() {
+        let level_no = level_idx + 1;
+        let out_path = base_out.join(format!("{}.vol", level_no));
+        let new_w = cur_w >> 1;
+        let new_h = cur_h >> 1;
+
+        // Resume support: a level is only ever written whole, so it either matches the
+        // expected shape (done) or needs to be regenerated from scratch (no partial resume).
+        let already_done = matches!(
+            read_volume_header(&out_path),
+            Ok((_, w, h, slice_count))
+                if w as usize == new_w && h as usize == new_h && slice_count as usize == *pairs_expected
+        );
+
+        if already_done {
+            let mut g = done_units.lock().unwrap();
+            *g += (*pairs_expected as f64) * *weight;
+        } else {
+            let mut slices: Vec<(Vec<u8>, u16)> = Vec::with_capacity(*pairs_expected);
+            let mut last_reported_pct = -1.0;
+
+            for pair_idx in 0..*pairs_expected {
+                let (bytes, bit_depth) = if level_no == 1 {
+                    decode_pair_from_files(&files[pair_idx * 2], &files[pair_idx * 2 + 1], cur_w, cur_h).map_err(to_pyerr)?
+                } else {
+                    let prev_path = base_out.join(format!("{}.vol", level_no - 1));
+                    let (b0, pw, ph, bd0) = read_volume_slice_bytes(&prev_path, pair_idx * 2).map_err(to_pyerr)?;
+                    let (b1, _, _, bd1) = read_volume_slice_bytes(&prev_path, pair_idx * 2 + 1).map_err(to_pyerr)?;
+                    downsample_pair(&b0, bd0, &b1, bd1, pw as usize, ph as usize)
+                };
+                slices.push((bytes, bit_depth));
+
+                let new_done = {
+                    let mut g = done_units.lock().unwrap();
+                    *g += *weight;
+                    *g
+                };
+                let pct = percent(new_done, total_units);
+                if let Some(cb) = &py_progress_cb {
+                    if pair_idx == *pairs_expected - 1 || (pct - last_reported_pct).abs() > 1.0 {
+                        Python::with_gil(|py| { let _ = cb.call1(py, (pct,)); });
+                        last_reported_pct = pct;
+                    }
+                }
+            }
+
+            // A level may legitimately mix 8-bit and 16-bit pairs (see downsample_pair); the
+            // container stores one bit depth per level, so promote every slice to the level's
+            // max depth before writing rather than letting the last pair's depth win.
+            let level_bit_depth = slices.iter().map(|(_, bd)| *bd).max().unwrap_or(8);
+            let slices: Vec<Vec<u8>> = slices
+                .into_iter()
+                .map(|(bytes, bd)| {
+                    if bd == level_bit_depth {
+                        bytes
+                    } else {
+                        widen_to_u16(&bytes, bd).iter().flat_map(|v| v.to_be_bytes()).collect()
+                    }
+                })
+                .collect();
+
+            write_volume_file(&out_path, new_w as u32, new_h as u32, level_bit_depth, &slices).map_err(to_pyerr)?;
+        }
+
+        cur_w = new_w;
+        cur_h = new_h;
+        if cur_w <= 500 && cur_h <= 500 {
+            break;
+        }
+    }
+
+    if let Some(cb) = py_progress_cb {
+        Python::with_gil(|py| {
+            let _ = cb.call1(py, (100.0_f64,));
+        });
+    }
+
+    Ok(())
+}
+
+/// Decode a single slice from a packed `.vol` file by index, returning its raw pixel
+/// bytes (big-endian u16 samples when `bit_depth == 16`) plus width, height, bit_depth.
+#[pyfunction]
+fn read_volume_slice(path: String, index: usize) -> PyResult<(Vec<u8>, u32, u32, u16)> {
+    read_volume_slice_bytes(&PathBuf::from(path), index).map_err(to_pyerr)
+}
+
 #[pymodule]
 fn ct_thumbnail(_py: Python<'_>, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(build_thumbnails, m)?)?;
+    m.add_function(wrap_pyfunction!(build_thumbnails_volume, m)?)?;
+    m.add_function(wrap_pyfunction!(read_volume_slice, m)?)?;
     Ok(())
 }
@@ -6,12 +6,13 @@ use pyo3::types::PyModule;
 use pyo3::{wrap_pyfunction, Bound};
 use rayon::prelude::*;
 use std::cmp::min;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use thiserror::Error;
+use tiff::encoder::{colortype, compression as tiff_compression, TiffEncoder};
 use walkdir::WalkDir;
 
 #[derive(Error, Debug)]
@@ -20,12 +21,28 @@ enum ThumbError {
     Io(#[from] std::io::Error),
     #[error("Image error: {0}")]
     Img(#[from] image::ImageError),
+    #[error("TIFF encoding error: {0}")]
+    Tiff(#[from] tiff::TiffError),
     #[error("Empty input folder")]
     Empty,
     #[error("Dimension mismatch: expected {0}x{1}, got {2}x{3}")]
     Dim(usize, usize, usize, usize),
 }
 
+// Tile compression used when writing pyramid levels; "none" keeps the original uncompressed TIFF
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TiffCompression {
+    None,
+    PackBits,
+}
+
+fn parse_compression(opt: Option<&str>) -> TiffCompression {
+    match opt.map(|s| s.to_ascii_lowercase()).as_deref() {
+        Some("packbits") => TiffCompression::PackBits,
+        _ => TiffCompression::None,
+    }
+}
+
 fn to_pyerr(e: ThumbError) -> PyErr {
     pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
 }
@@ -36,6 +53,121 @@ enum ImageDepth {
     Luma16(Vec<u16>),
 }
 
+static CRC32_TABLE: OnceLock<[u32; 256]> = OnceLock::new();
+
+fn crc32_table() -> &'static [u32; 256] {
+    CRC32_TABLE.get_or_init(|| {
+        let mut table = [0u32; 256];
+        for (n, entry) in table.iter_mut().enumerate() {
+            let mut a = n as u32;
+            for _ in 0..8 {
+                a = if a & 1 == 1 { 0xEDB8_8320 ^ (a >> 1) } else { a >> 1 };
+            }
+            *entry = a;
+        }
+        table
+    })
+}
+
+// Standard IEEE CRC32 (reflected, polynomial 0xEDB88320)
+fn crc32(bytes: &[u8]) -> u32 {
+    let table = crc32_table();
+    !bytes
+        .iter()
+        .fold(0xFFFF_FFFFu32, |a, &b| (a >> 8) ^ table[((a ^ b as u32) & 0xFF) as usize])
+}
+
+// Checksum the raw pixel buffer backing an ImageDepth, independent of TIFF encoding
+fn crc32_of_depth(depth: &ImageDepth) -> u32 {
+    match depth {
+        ImageDepth::Luma8(buf) => crc32(buf),
+        ImageDepth::Luma16(buf) => {
+            let bytes: Vec<u8> = buf.iter().flat_map(|v| v.to_be_bytes()).collect();
+            crc32(&bytes)
+        }
+    }
+}
+
+// FNV-1a, 64-bit variant
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |h, &b| (h ^ b as u64).wrapping_mul(PRIME))
+}
+
+// A wider (64-bit) content hash used only for cross-level dedup, kept separate from the
+// 32-bit CRC in the resume manifest: at the tile counts a deep pyramid reaches, CRC32's
+// collision bound is within reach, and a collision here must not share a hash with the
+// one the manifest uses to verify tile integrity, or a collision would defeat both checks
+// at once (hard-linking two distinct tiles together, and resume trusting the blended result).
+fn dedup_hash_of_depth(depth: &ImageDepth) -> u64 {
+    match depth {
+        ImageDepth::Luma8(buf) => fnv1a64(buf),
+        ImageDepth::Luma16(buf) => {
+            let bytes: Vec<u8> = buf.iter().flat_map(|v| v.to_be_bytes()).collect();
+            fnv1a64(&bytes)
+        }
+    }
+}
+
+fn manifest_path(level_dir: &Path) -> PathBuf {
+    level_dir.join("manifest.txt")
+}
+
+fn read_manifest(level_dir: &Path) -> HashMap<String, u32> {
+    let mut entries = HashMap::new();
+    if let Ok(contents) = fs::read_to_string(manifest_path(level_dir)) {
+        for line in contents.lines() {
+            if let Some((name, crc_hex)) = line.split_once(' ') {
+                if let Ok(crc) = u32::from_str_radix(crc_hex.trim(), 16) {
+                    entries.insert(name.to_string(), crc);
+                }
+            }
+        }
+    }
+    entries
+}
+
+// In-memory manifest entries keyed by level dir, so a level's manifest is parsed once
+// (lazily, on first tile) and rewritten in one pass rather than once per tile.
+type ManifestCache = Arc<Mutex<HashMap<PathBuf, HashMap<String, u32>>>>;
+
+// Record a tile's checksum in the in-memory manifest cache; does not write to disk (the
+// level's existing manifest is read on first use). Call `flush_manifest_cache` to persist.
+fn record_tile_checksum(cache: &ManifestCache, level_dir: &Path, name: &str, crc: u32) {
+    let mut cached = cache.lock();
+    cached
+        .entry(level_dir.to_path_buf())
+        .or_insert_with(|| read_manifest(level_dir))
+        .insert(name.to_string(), crc);
+}
+
+// Write every cached level manifest out to disk, atomically via rename
+fn flush_manifest_cache(cache: &ManifestCache) -> Result<(), ThumbError> {
+    let cached = cache.lock();
+    for (level_dir, entries) in cached.iter() {
+        let mut contents = String::with_capacity(entries.len() * 24);
+        for (entry_name, entry_crc) in entries {
+            contents.push_str(entry_name);
+            contents.push(' ');
+            contents.push_str(&format!("{:08x}\n", entry_crc));
+        }
+
+        let tmp_path = level_dir.join("manifest.txt.tmp");
+        fs::write(&tmp_path, contents)?;
+        fs::rename(&tmp_path, manifest_path(level_dir))?;
+    }
+    Ok(())
+}
+
+// Verify an on-disk tile against its recorded checksum; returns false on any read/decode error
+fn verify_tile_crc(path: &Path, expected: u32) -> bool {
+    match read_luma_preserve_depth(path) {
+        Ok((_, _, depth)) => crc32_of_depth(&depth) == expected,
+        Err(_) => false,
+    }
+}
+
 #[inline]
 fn to_luma_preserve_depth(img: DynamicImage) -> (ImageDepth, u32, u32) {
     match img {
@@ -194,7 +326,16 @@ fn read_luma_preserve_depth(path: &Path) -> Result<(usize, usize, ImageDepth), T
     Ok((w as usize, h as usize, depth))
 }
 
-fn write_tiff_preserve_depth(path: &Path, w: u32, h: u32, depth: &ImageDepth) -> Result<(), ThumbError> {
+fn write_tiff_preserve_depth(
+    path: &Path,
+    w: u32,
+    h: u32,
+    depth: &ImageDepth,
+    compression: TiffCompression,
+) -> Result<(), ThumbError> {
+    if compression == TiffCompression::PackBits {
+        return write_tiff_packbits(path, w, h, depth);
+    }
     match depth {
         ImageDepth::Luma8(buf) => {
             let img = ImageBuffer::<Luma<u8>, _>::from_raw(w, h, buf.to_vec())
@@ -210,6 +351,33 @@ fn write_tiff_preserve_depth(path: &Path, w: u32, h: u32, depth: &ImageDepth) ->
     Ok(())
 }
 
+// CT thumbnails are dominated by flat background, so PackBits run-length encoding
+// typically shrinks tiles substantially for negligible CPU cost while staying a
+// standard, widely-readable TIFF.
+fn write_tiff_packbits(path: &Path, w: u32, h: u32, depth: &ImageDepth) -> Result<(), ThumbError> {
+    let file = fs::File::create(path)?;
+    let mut encoder = TiffEncoder::new(file)?;
+    match depth {
+        ImageDepth::Luma8(buf) => {
+            encoder.write_image_with_compression::<colortype::Gray8, _>(
+                w,
+                h,
+                tiff_compression::Packbits,
+                buf,
+            )?;
+        },
+        ImageDepth::Luma16(buf) => {
+            encoder.write_image_with_compression::<colortype::Gray16, _>(
+                w,
+                h,
+                tiff_compression::Packbits,
+                buf,
+            )?;
+        }
+    }
+    Ok(())
+}
+
 fn ensure_dir(p: &Path) -> Result<(), ThumbError> {
     fs::create_dir_all(p)?;
     Ok(())
@@ -219,18 +387,25 @@ fn level_dir(base: &Path, level: usize) -> PathBuf {
     base.join(level.to_string())
 }
 
-// Collect existing files in each level directory for resume support
+// Collect existing, checksum-verified files in each level directory for resume support.
+// A tile without a matching manifest entry (missing or corrupt) is treated as absent
+// so it gets regenerated instead of silently trusted.
 fn collect_existing_files(base_out: &Path, max_levels: usize) -> Vec<HashSet<String>> {
     let mut existing = Vec::new();
     for level in 1..=max_levels {
         let dir = level_dir(base_out, level);
         let mut files = HashSet::new();
         if dir.exists() {
-            if let Ok(entries) = fs::read_dir(dir) {
+            let manifest = read_manifest(&dir);
+            if let Ok(entries) = fs::read_dir(&dir) {
                 for entry in entries.flatten() {
                     if let Some(name) = entry.file_name().to_str() {
                         if name.ends_with(".tif") || name.ends_with(".tiff") {
-                            files.insert(name.to_string());
+                            if let Some(&expected_crc) = manifest.get(name) {
+                                if verify_tile_crc(&entry.path(), expected_crc) {
+                                    files.insert(name.to_string());
+                                }
+                            }
                         }
                     }
                 }
@@ -241,6 +416,58 @@ fn collect_existing_files(base_out: &Path, max_levels: usize) -> Vec<HashSet<Str
     existing
 }
 
+// Key for the cross-level dedup table: dimensions + bit depth + content hash. Uses
+// `dedup_hash_of_depth`, not the manifest's CRC32, so a hash collision can't both
+// mis-dedup a tile and slip past resume's integrity check at the same time.
+type DedupKey = (u32, u32, u8, u64);
+type DedupMap = Arc<Mutex<HashMap<DedupKey, PathBuf>>>;
+
+fn depth_tag(depth: &ImageDepth) -> u8 {
+    match depth {
+        ImageDepth::Luma8(_) => 0,
+        ImageDepth::Luma16(_) => 1,
+    }
+}
+
+// Write a tile, hard-linking to an earlier byte-identical tile when one already exists,
+// and record its checksum in the level manifest either way
+fn write_tile_with_manifest(
+    level_dir: &Path,
+    output_path: &Path,
+    output_name: &str,
+    w: u32,
+    h: u32,
+    depth: &ImageDepth,
+    dedup: &DedupMap,
+    manifest: &ManifestCache,
+    compression: TiffCompression,
+) -> Result<(), ThumbError> {
+    let crc = crc32_of_depth(depth);
+    let key = (w, h, depth_tag(depth), dedup_hash_of_depth(depth));
+
+    let existing_path = {
+        let mut map = dedup.lock();
+        match map.get(&key).cloned() {
+            Some(p) => Some(p),
+            None => {
+                map.insert(key, output_path.to_path_buf());
+                None
+            }
+        }
+    };
+
+    match existing_path {
+        Some(src) if fs::hard_link(&src, output_path).is_ok() => {}
+        Some(src) => {
+            fs::copy(&src, output_path)?;
+        }
+        None => write_tiff_preserve_depth(output_path, w, h, depth, compression)?,
+    }
+
+    record_tile_checksum(manifest, level_dir, output_name, crc);
+    Ok(())
+}
+
 // Process a group of images and generate all levels in memory
 fn process_group_all_levels(
     group_files: &[PathBuf],
@@ -254,6 +481,9 @@ fn process_group_all_levels(
     total_units: f64,
     done_units: Arc<Mutex<f64>>,
     py_callback: Option<&PyObject>,
+    dedup: &DedupMap,
+    manifest: &ManifestCache,
+    compression: TiffCompression,
 ) -> Result<(), ThumbError> {
     if group_files.is_empty() || levels_needed == 0 {
         return Ok(());
@@ -456,7 +686,7 @@ fn process_group_all_levels(
                 if let Some(result) = result {
                     // Save the result
                     let output_path = level_dir.join(&output_name);
-                    write_tiff_preserve_depth(&output_path, new_w as u32, new_h as u32, &result)?;
+                    write_tile_with_manifest(&level_dir, &output_path, &output_name, new_w as u32, new_h as u32, &result, dedup, manifest, compression)?;
 
                     // Keep for next level processing
                     next_images.push(result);
@@ -542,7 +772,7 @@ fn process_group_all_levels(
 
                 if let Some(result) = result {
                     let output_path = level_dir.join(&output_name);
-                    write_tiff_preserve_depth(&output_path, new_w as u32, new_h as u32, &result)?;
+                    write_tile_with_manifest(&level_dir, &output_path, &output_name, new_w as u32, new_h as u32, &result, dedup, manifest, compression)?;
                     next_images.push(result);
                 }
             }
@@ -564,7 +794,7 @@ fn process_group_all_levels(
 
 /// Build thumbnails with optimized group-based processing
 #[pyfunction]
-#[pyo3(signature = (input_dir, py_progress_cb=None, prefix=None, file_type=None, seq_begin=None, seq_end=None, index_length=None))]
+#[pyo3(signature = (input_dir, py_progress_cb=None, prefix=None, file_type=None, seq_begin=None, seq_end=None, index_length=None, compression=None))]
 fn build_thumbnails_optimized(
     input_dir: String,
     py_progress_cb: Option<PyObject>,
@@ -573,7 +803,9 @@ fn build_thumbnails_optimized(
     seq_begin: Option<usize>,
     seq_end: Option<usize>,
     index_length: Option<usize>,
+    compression: Option<String>,
 ) -> PyResult<()> {
+    let compression = parse_compression(compression.as_deref());
 
     let input_dir = PathBuf::from(&input_dir);
 
@@ -637,6 +869,13 @@ fn build_thumbnails_optimized(
 
     let done_units = Arc::new(Mutex::new(0.0));
 
+    // Shared across groups/levels so byte-identical tiles are hard-linked instead of re-encoded
+    let dedup: DedupMap = Arc::new(Mutex::new(HashMap::new()));
+
+    // Shared across groups/levels so each level's manifest is parsed once and rewritten
+    // once per group instead of once per tile
+    let manifest: ManifestCache = Arc::new(Mutex::new(HashMap::new()));
+
     // Initial callback
     if let Some(cb) = &py_progress_cb {
         Python::with_gil(|py| { let _ = cb.call1(py, (0.0_f64,)); });
@@ -668,7 +907,14 @@ fn build_thumbnails_optimized(
             total_units,
             done_units.clone(),
             py_progress_cb.as_ref(),
+            &dedup,
+            &manifest,
+            compression,
         ).map_err(to_pyerr)?;
+
+        // Flush manifests once per group rather than once per tile; still checkpoints
+        // progress for resume after a crash or cancellation mid-run.
+        flush_manifest_cache(&manifest).map_err(to_pyerr)?;
     }
 
     // Final callback
@@ -683,7 +929,7 @@ fn build_thumbnails_optimized(
 
 // Keep original function for compatibility
 #[pyfunction]
-#[pyo3(signature = (input_dir, py_progress_cb=None, prefix=None, file_type=None, seq_begin=None, seq_end=None, index_length=None))]
+#[pyo3(signature = (input_dir, py_progress_cb=None, prefix=None, file_type=None, seq_begin=None, seq_end=None, index_length=None, compression=None))]
 fn build_thumbnails(
     input_dir: String,
     py_progress_cb: Option<PyObject>,
@@ -692,6 +938,7 @@ fn build_thumbnails(
     seq_begin: Option<usize>,
     seq_end: Option<usize>,
     index_length: Option<usize>,
+    compression: Option<String>,
 ) -> PyResult<()> {
     // Use optimized version by default
     build_thumbnails_optimized(
@@ -702,6 +949,7 @@ fn build_thumbnails(
         seq_begin,
         seq_end,
         index_length,
+        compression,
     )
 }
 